@@ -18,14 +18,49 @@
 //! kernel_println!("{} + {} = {}", 2, 2, 2 + 2);
 //! ```
 //!
+//! ## Severity levels
+//!
+//! In addition to the plain `kernel_print!`/`kernel_println!` pair (which are
+//! tagged at the info level), a full ladder of severity macros is available,
+//! mirroring the Linux kernel's `pr_*!` macros: [`kernel_emerg!`],
+//! [`kernel_alert!`], [`kernel_crit!`], [`kernel_err!`], [`kernel_warn!`],
+//! [`kernel_notice!`], [`kernel_info!`] and [`kernel_debug!`]. Each prepends a
+//! `KERN_*`-style tag to the formatted message, so a backend can filter or
+//! color by level instead of treating every line the same.
+//!
+//! There's also an `eprint!`/`eprintln!` style error channel:
+//! [`kernel_eprint!`] and [`kernel_eprintln!`] are tagged at the error level,
+//! the same tag as [`writer::KernelWriter::new_err`]. They write through the
+//! same backend as the normal output; only the level tag sets them apart.
+//!
 //! ## Features
 //!
 //! - `std_name`: Allows you to use the macros without the `kernel_` prefix.
 //! - `format`: Uses the `format!` macro instead of the `core::fmt::Write` trait
 //!   to convert the passed data into a string.
+//! - `port_e9`: Registers a default [`writer::KernelOutput`] that writes to
+//!   the Bochs/QEMU debug console (I/O port `0xE9`), so the crate produces
+//!   output out of the box without calling [`writer::set_output`] yourself.
+//!
+//! ## Output backend
+//!
+//! All macros ultimately write through whatever backend was registered with
+//! [`writer::set_output`] - a VGA text buffer, a UART, a framebuffer, a host
+//! debug channel, anything implementing [`writer::KernelOutput`]. Until one
+//! is registered (and without the `port_e9` feature), writes are silently
+//! dropped.
+//!
+//! ## Targeting an explicit writer
+//!
+//! Every macro above goes through the implicit console writer, but
+//! [`kernel_write!`]/[`kernel_writeln!`] (and [`kernel_dbg_to!`]) take a
+//! `&mut impl core::fmt::Write` as their first argument instead, so output
+//! can be captured into a ring buffer, a per-subsystem log, or any other
+//! `fmt::Write` implementor - including your own [`writer::KernelWriter`].
 
 #![no_std]
 
+#[cfg(feature = "format")]
 extern crate alloc;
 
 #[doc(hidden)] pub mod writer;
@@ -33,7 +68,14 @@ extern crate alloc;
 #[cfg(feature = "std_name")]
 #[doc(hidden)]
 pub mod std_name {
-    pub use super::{kernel_dbg as dbg, kernel_print as print, kernel_println as println};
+    pub use super::{
+        kernel_alert as alert, kernel_crit as crit, kernel_dbg_to as dbg_to,
+        kernel_debug as debug, kernel_dbg as dbg, kernel_emerg as emerg,
+        kernel_eprint as eprint, kernel_eprintln as eprintln, kernel_err as err,
+        kernel_notice as notice, kernel_info as info, kernel_print as print,
+        kernel_println as println, kernel_warn as warn, kernel_write as write,
+        kernel_writeln as writeln,
+    };
 }
 
 #[cfg(feature = "std_name")] pub use std_name::*;
@@ -67,6 +109,34 @@ macro_rules! kernel_dbg {
     };
 }
 
+/// Like [`kernel_dbg!`], but writes to an explicit `impl core::fmt::Write`
+/// instead of the console, so instrumentation can be routed to a ring
+/// buffer, a per-subsystem log, or any other [`kernel_write!`] target.
+///
+/// Does not panic on failure to write - instead silently ignores errors.
+#[macro_export]
+macro_rules! kernel_dbg_to {
+    ($writer:expr $(,)?) => {
+        let _ = $crate::kernel_writeln!($writer, "[{}:{}]", file!(), line!());
+    };
+    ($writer:expr, $val:expr) => {
+        // Use of `match` here is intentional because it affects the lifetimes
+        // of temporaries - https://stackoverflow.com/a/48732525/1063961
+        match $val {
+            tmp => {
+                let _ = $crate::kernel_writeln!($writer, "[{}:{}] {} = {:#?}",
+                    file!(), line!(), stringify!($val), &tmp);
+                tmp
+            }
+        }
+    };
+    // Trailing comma with single argument is ignored
+    ($writer:expr, $val:expr,) => { $crate::kernel_dbg_to!($writer, $val) };
+    ($writer:expr, $($val:expr),+ $(,)?) => {
+        ($($crate::kernel_dbg_to!($writer, $val)),+,)
+    };
+}
+
 /// Prints to the standard output.
 ///
 /// Does not panic on failure to write - instead silently ignores errors.
@@ -85,7 +155,7 @@ macro_rules! kernel_print {
 macro_rules! __impl_print {
     ($($arg:tt)*) => {
         {
-            let mut writer = $crate::writer::KernelWriter::new();
+            let mut writer = $crate::writer::__kernel_print_lock_with($crate::writer::Level::Info);
             let _ = writer.write_fmt(format_args!($($arg)*));
         }
     };
@@ -124,9 +194,9 @@ macro_rules! kernel_println {
 macro_rules! __impl_println {
     ($($arg:tt)*) => {
         {
-            let mut writer = $crate::writer::KernelWriter::new();
+            let mut writer = $crate::writer::__kernel_print_lock_with($crate::writer::Level::Info);
             let _ = writer.write_fmt(format_args!($($arg)*));
-            let _ = writer.write_nl();
+            let _ = writer.write_str("\n");
         }
     };
 }
@@ -146,3 +216,241 @@ macro_rules! __impl_println {
         }
     };
 }
+
+/// Prints to the error output.
+///
+/// Tagged at the error level (like [`writer::KernelWriter::new_err`]). It
+/// writes through the same backend as [`kernel_print!`]; only the level tag
+/// sets the two apart.
+///
+/// Does not panic on failure to write - instead silently ignores errors.
+///
+/// See [`eprint!`](https://doc.rust-lang.org/std/macro.eprint.html) for full documentation.
+#[macro_export]
+macro_rules! kernel_eprint {
+    ($($arg:tt)*) => {
+        $crate::__impl_eprint!($($arg)*);
+    };
+}
+
+#[cfg(not(feature = "format"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_eprint {
+    ($($arg:tt)*) => {
+        {
+            let mut writer = $crate::writer::__kernel_print_lock_with($crate::writer::Level::Error);
+            let _ = writer.write_fmt(format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(feature = "format")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_eprint {
+    ($($arg:tt)*) => {
+        {
+            let out = alloc::format!($($arg)*);
+            let _ = $crate::writer::__kernel_println_level($crate::writer::Level::Error, out);
+        }
+    };
+}
+
+/// Prints to the error output, with a newline.
+///
+/// Tagged at the error level (like [`writer::KernelWriter::new_err`]). It
+/// writes through the same backend as [`kernel_println!`]; only the level tag
+/// sets the two apart.
+///
+/// Does not panic on failure to write - instead silently ignores errors.
+///
+/// See [`eprintln!`](https://doc.rust-lang.org/std/macro.eprintln.html) for full documentation.
+#[macro_export]
+macro_rules! kernel_eprintln {
+    () => {
+        $crate::kernel_eprintln!("")
+    };
+    ($($arg:tt)*) => {
+        $crate::__impl_eprintln!($($arg)*);
+    };
+}
+
+#[cfg(not(feature = "format"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_eprintln {
+    ($($arg:tt)*) => {
+        {
+            let mut writer = $crate::writer::__kernel_print_lock_with($crate::writer::Level::Error);
+            let _ = writer.write_fmt(format_args!($($arg)*));
+            let _ = writer.write_str("\n");
+        }
+    };
+}
+
+#[cfg(feature = "format")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_eprintln {
+    ($($arg:tt)*) => {
+        {
+            let out = {
+                let mut out = alloc::format!($($arg)*);
+                out.push('\n');
+                out
+            };
+            let _ = $crate::writer::__kernel_println_level($crate::writer::Level::Error, out);
+        }
+    };
+}
+
+#[cfg(not(feature = "format"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_level_println {
+    ($level:expr, $($arg:tt)*) => {
+        {
+            let mut writer = $crate::writer::__kernel_print_lock_with($level);
+            let _ = writer.write_fmt(format_args!($($arg)*));
+            let _ = writer.write_str("\n");
+        }
+    };
+}
+
+#[cfg(feature = "format")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __impl_level_println {
+    ($level:expr, $($arg:tt)*) => {
+        {
+            let out = {
+                let mut out = alloc::format!($($arg)*);
+                out.push('\n');
+                out
+            };
+            let _ = $crate::writer::__kernel_println_level($level, out);
+        }
+    };
+}
+
+/// Writes formatted output to an explicit `impl core::fmt::Write`, instead of
+/// the implicit console writer the other `kernel_*!` macros use.
+///
+/// This is just [`core::write!`] under a matching name - it's provided so
+/// code that wants to redirect output to its own [`writer::KernelWriter`] (or
+/// any other writer, such as a ring buffer) doesn't have to mix `kernel_`-
+/// and `core`-prefixed macros. Keeps the zero-allocation path intact, since
+/// it delegates straight to `write_fmt` on the supplied writer.
+///
+/// See [`write!`](https://doc.rust-lang.org/std/macro.write.html) for full documentation.
+#[macro_export]
+macro_rules! kernel_write {
+    ($writer:expr, $($arg:tt)*) => {
+        core::write!($writer, $($arg)*)
+    };
+}
+
+/// Like [`kernel_write!`], but appends a newline.
+///
+/// See [`writeln!`](https://doc.rust-lang.org/std/macro.writeln.html) for full documentation.
+#[macro_export]
+macro_rules! kernel_writeln {
+    ($writer:expr $(,)?) => {
+        core::writeln!($writer)
+    };
+    ($writer:expr, $($arg:tt)*) => {
+        core::writeln!($writer, $($arg)*)
+    };
+}
+
+/// Prints an emergency-level message: the system is unusable.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_emerg {
+    () => { $crate::kernel_emerg!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Emergency, $($arg)*);
+    };
+}
+
+/// Prints an alert-level message: action must be taken immediately.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_alert {
+    () => { $crate::kernel_alert!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Alert, $($arg)*);
+    };
+}
+
+/// Prints a critical-level message.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_crit {
+    () => { $crate::kernel_crit!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Critical, $($arg)*);
+    };
+}
+
+/// Prints an error-level message.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_err {
+    () => { $crate::kernel_err!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Error, $($arg)*);
+    };
+}
+
+/// Prints a warning-level message.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_warn {
+    () => { $crate::kernel_warn!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Warning, $($arg)*);
+    };
+}
+
+/// Prints a notice-level message: normal but significant condition.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_notice {
+    () => { $crate::kernel_notice!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Notice, $($arg)*);
+    };
+}
+
+/// Prints an info-level message.
+///
+/// Equivalent to [`kernel_println!`], which also tags its output at the info
+/// level - this macro exists so the full severity ladder reads consistently.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_info {
+    () => { $crate::kernel_info!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Info, $($arg)*);
+    };
+}
+
+/// Prints a debug-level message.
+///
+/// See [`kernel_println!`] for the formatting rules.
+#[macro_export]
+macro_rules! kernel_debug {
+    () => { $crate::kernel_debug!("") };
+    ($($arg:tt)*) => {
+        $crate::__impl_level_println!($crate::writer::Level::Debug, $($arg)*);
+    };
+}
@@ -0,0 +1,248 @@
+//! The actual writer implementation used by the `kernel_print!`/`kernel_println!`
+//! family of macros.
+//!
+//! This is kept separate from `lib.rs` so that the macro definitions don't have
+//! to know anything about how bytes actually leave the kernel.
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Severity of a single kernel message.
+///
+/// Mirrors the log levels used by the Linux kernel's `pr_*!` macros (and, by
+/// extension, `printk`'s `KERN_*` prefixes), so the numeric tag written in
+/// front of each message is directly comparable to what `dmesg` shows. The tag
+/// is written once per writer, before its first write, not after every newline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Emergency,
+    Alert,
+    Critical,
+    Error,
+    Warning,
+    Notice,
+    Info,
+    Debug,
+}
+
+impl Level {
+    /// The `KERN_*`-style prefix prepended to every message of this level.
+    fn prefix(self) -> &'static str {
+        match self {
+            Level::Emergency => "<0>",
+            Level::Alert => "<1>",
+            Level::Critical => "<2>",
+            Level::Error => "<3>",
+            Level::Warning => "<4>",
+            Level::Notice => "<5>",
+            Level::Info => "<6>",
+            Level::Debug => "<7>",
+        }
+    }
+}
+
+/// Writes formatted output to the kernel console, tagged with a [`Level`].
+///
+/// Constructed fresh for every console-writing `kernel_*!` macro invocation - see the
+/// `__impl_*` macros in the crate root.
+pub struct KernelWriter {
+    level: Level,
+    prefixed: bool,
+}
+
+impl KernelWriter {
+    /// Creates a writer for the default, info-level channel.
+    pub fn new() -> Self {
+        Self::with_level(Level::Info)
+    }
+
+    /// Creates a writer for the error channel, tagged at the error level.
+    ///
+    /// Uses the same level as [`kernel_eprint!`](crate::kernel_eprint!)/[`kernel_eprintln!`](crate::kernel_eprintln!),
+    /// mirroring the `print!`/`eprint!` split from the standard library. It
+    /// writes through the same backend as [`KernelWriter::new`].
+    pub fn new_err() -> Self {
+        Self::with_level(Level::Error)
+    }
+
+    /// Creates a writer tagged with an explicit [`Level`].
+    pub fn with_level(level: Level) -> Self {
+        Self { level, prefixed: false }
+    }
+
+    /// Writes a trailing newline.
+    pub fn write_nl(&mut self) -> fmt::Result {
+        self.write_str("\n")
+    }
+}
+
+impl Default for KernelWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for KernelWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.prefixed {
+            self.prefixed = true;
+            raw_write(self.level.prefix().as_bytes());
+        }
+
+        raw_write(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// A minimal interrupt-unsafe spinlock, used both to serialize console
+/// output and to guard the registered [`KernelOutput`] backend.
+struct Spinlock(AtomicBool);
+
+impl Spinlock {
+    const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    fn acquire(&self) {
+        while self
+            .0
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+    }
+
+    fn release(&self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+static LOCK: Spinlock = Spinlock::new();
+
+/// A held lock on the kernel console, writing at a fixed [`Level`].
+///
+/// Returned by [`kernel_print_lock`]. Every console-writing `kernel_*!` macro
+/// invocation acquires one of these (and drops it again) internally so a single
+/// `kernel_println!` call is never interleaved with output from another CPU;
+/// hold one yourself across several `write!`/`writeln!` calls to amortize
+/// that locking cost in a hot path, the same way you'd hold
+/// `io::stdout().lock()`.
+pub struct KernelPrintLock(KernelWriter);
+
+impl KernelPrintLock {
+    fn new(level: Level) -> Self {
+        LOCK.acquire();
+        Self(KernelWriter::with_level(level))
+    }
+}
+
+impl Write for KernelPrintLock {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+impl Drop for KernelPrintLock {
+    fn drop(&mut self) {
+        LOCK.release();
+    }
+}
+
+/// Acquires the global kernel print lock, at the info level.
+///
+/// See [`KernelPrintLock`] for why (and when) you'd want to hold onto this
+/// yourself instead of calling [`crate::kernel_println!`] repeatedly.
+pub fn kernel_print_lock() -> KernelPrintLock {
+    KernelPrintLock::new(Level::Info)
+}
+
+/// Used by the `__impl_*` macros so every `kernel_*!` invocation is
+/// serialized for its whole duration, not just per fragment.
+#[doc(hidden)]
+pub fn __kernel_print_lock_with(level: Level) -> KernelPrintLock {
+    KernelPrintLock::new(level)
+}
+
+/// A hardware (or host) sink that kernel output gets written to.
+///
+/// Implement this for whatever console the target kernel has - a VGA text
+/// buffer, a UART/serial port, a framebuffer, a host debug channel, ... -
+/// and register it once with [`set_output`]. Everything in this crate then
+/// writes through it without needing to know it exists.
+pub trait KernelOutput: Sync {
+    fn write_bytes(&self, buf: &[u8]);
+}
+
+static OUTPUT_LOCK: Spinlock = Spinlock::new();
+static mut OUTPUT: Option<&'static dyn KernelOutput> = None;
+
+/// Registers the backend that all `kernel_*!` macros write through.
+///
+/// Call this once, e.g. during boot - a later call simply replaces the
+/// previous backend. Until it's called (and unless the `port_e9` feature is
+/// enabled, which supplies a default), writes are silently dropped.
+///
+/// Guarded by its own spinlock rather than an allocation, so this works even
+/// without a global allocator configured.
+pub fn set_output(output: &'static dyn KernelOutput) {
+    OUTPUT_LOCK.acquire();
+    unsafe { OUTPUT = Some(output) };
+    OUTPUT_LOCK.release();
+}
+
+fn output() -> Option<&'static dyn KernelOutput> {
+    OUTPUT_LOCK.acquire();
+    let registered = unsafe { OUTPUT };
+    OUTPUT_LOCK.release();
+
+    registered.or({
+        #[cfg(feature = "port_e9")]
+        {
+            Some(&PortE9 as &dyn KernelOutput)
+        }
+        #[cfg(not(feature = "port_e9"))]
+        {
+            None
+        }
+    })
+}
+
+fn raw_write(buf: &[u8]) {
+    if let Some(out) = output() {
+        out.write_bytes(buf);
+    }
+}
+
+/// Default backend writing to the Bochs/QEMU debug console (I/O port
+/// `0xE9`), enabled via the `port_e9` feature. Kept so the crate behaves the
+/// same out of the box as before [`KernelOutput`] existed.
+#[cfg(feature = "port_e9")]
+struct PortE9;
+
+#[cfg(feature = "port_e9")]
+impl KernelOutput for PortE9 {
+    fn write_bytes(&self, buf: &[u8]) {
+        for &byte in buf {
+            unsafe {
+                core::arch::asm!("out 0xe9, al", in("al") byte, options(nomem, nostack, preserves_flags));
+            }
+        }
+    }
+}
+
+/// Used by the `format` feature, which builds the whole message up-front via
+/// `alloc::format!` instead of writing fragment-by-fragment.
+///
+/// Note that any trailing newline is expected to already be part of `s` - see
+/// the `__impl_print`/`__impl_println` split in the crate root.
+#[cfg(feature = "format")]
+pub fn __kernel_println(s: alloc::string::String) -> fmt::Result {
+    __kernel_println_level(Level::Info, s)
+}
+
+/// Leveled counterpart of [`__kernel_println`].
+#[cfg(feature = "format")]
+pub fn __kernel_println_level(level: Level, s: alloc::string::String) -> fmt::Result {
+    __kernel_print_lock_with(level).write_str(&s)
+}